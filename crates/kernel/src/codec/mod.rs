@@ -0,0 +1,48 @@
+#![forbid(unsafe_code)]
+
+pub mod encrypting;
+pub mod scalar;
+
+pub use encrypting::{AeadAlgorithm, EncryptingCodec};
+pub use scalar::{Conversion, ScalarCodec, ScalarCodecFactory};
+
+use crate::error::Result;
+use crate::models::Message;
+use crate::utils::structs::BTreeMap;
+use crate::wire::WireMessage;
+use async_trait::async_trait;
+use std::any::Any;
+
+#[async_trait]
+pub trait Codec: Send + Sync {
+    async fn encode_any(&self, value: &dyn Any) -> Result<Vec<u8>>;
+
+    async fn decode_any(&self, data: &[u8]) -> Result<Box<dyn Any>>;
+
+    fn as_any(&self) -> &dyn Any;
+
+    /// Encode into a full [`WireMessage`] rather than a bare payload, so codecs that need to
+    /// carry out-of-band metadata (nonces, algorithm ids, ...) can attach it via headers.
+    ///
+    /// The default wraps [`Codec::encode_any`]'s bytes with no extra headers.
+    async fn encode_message(&self, value: &dyn Any) -> Result<WireMessage> {
+        Ok(WireMessage {
+            headers: BTreeMap::new(),
+            payload: self.encode_any(value).await?,
+            correlation_id: None,
+            content_type: None,
+        })
+    }
+
+    /// Decode a full [`WireMessage`], letting codecs inspect headers before delegating to
+    /// [`Codec::decode_any`].
+    ///
+    /// The default ignores headers and decodes the raw payload.
+    async fn decode_message(&self, message: &WireMessage) -> Result<Box<dyn Any>> {
+        self.decode_any(&message.payload).await
+    }
+}
+
+pub trait CodecFactory: Send + Sync {
+    fn create_codec(&self, message: &Message) -> Result<Box<dyn Codec>>;
+}
@@ -0,0 +1,357 @@
+use super::{Codec, CodecFactory};
+use crate::error::{CodecError, Result};
+use crate::models::{Message, MessageSchema};
+use async_trait::async_trait;
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone, Utc};
+use std::any::Any;
+use std::str::FromStr;
+
+/// How a [`ScalarCodec`] converts bytes on the wire to and from a concrete Rust scalar.
+///
+/// Built from the `type`/`format` (and, for timestamps, the `x-timestamp-format` extension) of a
+/// resolved message schema via [`Conversion::from_schema`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Conversion {
+    /// No conversion; the raw payload bytes are passed through.
+    Bytes,
+    Integer,
+    Float,
+    Boolean,
+    /// RFC 3339 timestamp, e.g. `type: string, format: date-time`.
+    Timestamp,
+    /// Custom strftime-style pattern with no timezone offset; assumed to be in the local TZ.
+    TimestampFmt(String),
+    /// Custom strftime-style pattern that itself carries a timezone offset (e.g. `%z`).
+    TimestampTZFmt(String),
+}
+
+impl Conversion {
+    /// Derive a conversion from a resolved message schema's scalar fields.
+    ///
+    /// `x-timestamp-format` takes precedence over `type`/`format` and selects between
+    /// [`Conversion::TimestampFmt`] and [`Conversion::TimestampTZFmt`] depending on whether the
+    /// pattern itself encodes a timezone offset (`%z` or `%Z`).
+    pub fn from_schema(schema: &MessageSchema) -> Self {
+        if let Some(pattern) = schema.extensions.get("x-timestamp-format") {
+            return if pattern.contains("%z") || pattern.contains("%Z") || pattern.contains("%:z") {
+                Conversion::TimestampTZFmt(pattern.clone())
+            } else {
+                Conversion::TimestampFmt(pattern.clone())
+            };
+        }
+
+        match (schema.r#type.as_deref(), schema.format.as_deref()) {
+            (Some("integer"), _) => Conversion::Integer,
+            (Some("number"), _) => Conversion::Float,
+            (Some("boolean"), _) => Conversion::Boolean,
+            (Some("string"), Some("date-time")) => Conversion::Timestamp,
+            _ => Conversion::Bytes,
+        }
+    }
+}
+
+/// Converts a raw [`crate::wire::WireMessage`] payload to and from the scalar Rust type described
+/// by its resolved message schema.
+pub struct ScalarCodec {
+    conversion: Conversion,
+}
+
+impl ScalarCodec {
+    pub fn new(conversion: Conversion) -> Self {
+        Self { conversion }
+    }
+
+    fn payload_str(data: &[u8]) -> Result<&str> {
+        if data.is_empty() {
+            return Err(CodecError::EmptyPayload.into());
+        }
+        Ok(std::str::from_utf8(data).map_err(CodecError::InvalidUtf8)?)
+    }
+
+    fn parse_boolean(text: &str) -> Result<bool> {
+        match text {
+            "true" => Ok(true),
+            "false" => Ok(false),
+            other => Err(CodecError::InvalidBoolean(other.to_string()).into()),
+        }
+    }
+
+    fn parse_timestamp_fmt(text: &str, pattern: &str) -> Result<DateTime<Utc>> {
+        let naive = NaiveDateTime::parse_from_str(text, pattern).map_err(|_| {
+            CodecError::InvalidTimestamp {
+                value: text.to_string(),
+                pattern: pattern.to_string(),
+            }
+        })?;
+        Local
+            .from_local_datetime(&naive)
+            .single()
+            .map(|dt| dt.with_timezone(&Utc))
+            .ok_or_else(|| {
+                CodecError::InvalidTimestamp {
+                    value: text.to_string(),
+                    pattern: pattern.to_string(),
+                }
+                .into()
+            })
+    }
+
+    fn parse_timestamp_tz_fmt(text: &str, pattern: &str) -> Result<DateTime<Utc>> {
+        DateTime::parse_from_str(text, pattern)
+            .map(|dt| dt.with_timezone(&Utc))
+            .map_err(|_| {
+                CodecError::InvalidTimestamp {
+                    value: text.to_string(),
+                    pattern: pattern.to_string(),
+                }
+                .into()
+            })
+    }
+}
+
+#[async_trait]
+impl Codec for ScalarCodec {
+    async fn encode_any(&self, value: &dyn Any) -> Result<Vec<u8>> {
+        let encoded = match &self.conversion {
+            Conversion::Bytes => value
+                .downcast_ref::<Vec<u8>>()
+                .expect("value must be a Vec<u8> for Conversion::Bytes")
+                .clone(),
+            Conversion::Integer => value
+                .downcast_ref::<i64>()
+                .expect("value must be an i64 for Conversion::Integer")
+                .to_string()
+                .into_bytes(),
+            Conversion::Float => value
+                .downcast_ref::<f64>()
+                .expect("value must be an f64 for Conversion::Float")
+                .to_string()
+                .into_bytes(),
+            Conversion::Boolean => value
+                .downcast_ref::<bool>()
+                .expect("value must be a bool for Conversion::Boolean")
+                .to_string()
+                .into_bytes(),
+            Conversion::Timestamp => value
+                .downcast_ref::<DateTime<Utc>>()
+                .expect("value must be a DateTime<Utc> for Conversion::Timestamp")
+                .to_rfc3339()
+                .into_bytes(),
+            Conversion::TimestampFmt(pattern) => value
+                .downcast_ref::<DateTime<Utc>>()
+                .expect("value must be a DateTime<Utc> for Conversion::TimestampFmt")
+                .with_timezone(&Local)
+                .format(pattern)
+                .to_string()
+                .into_bytes(),
+            Conversion::TimestampTZFmt(pattern) => value
+                .downcast_ref::<DateTime<Utc>>()
+                .expect("value must be a DateTime<Utc> for Conversion::TimestampTZFmt")
+                .format(pattern)
+                .to_string()
+                .into_bytes(),
+        };
+        Ok(encoded)
+    }
+
+    async fn decode_any(&self, data: &[u8]) -> Result<Box<dyn Any>> {
+        if matches!(self.conversion, Conversion::Bytes) {
+            if data.is_empty() {
+                return Err(CodecError::EmptyPayload.into());
+            }
+            return Ok(Box::new(data.to_vec()));
+        }
+
+        let text = Self::payload_str(data)?;
+
+        let decoded: Box<dyn Any> = match &self.conversion {
+            Conversion::Bytes => unreachable!("handled above"),
+            Conversion::Integer => {
+                let parsed = i64::from_str(text).map_err(|err| {
+                    match err.kind() {
+                        std::num::IntErrorKind::PosOverflow
+                        | std::num::IntErrorKind::NegOverflow => {
+                            CodecError::IntegerOutOfRange(text.to_string())
+                        }
+                        _ => CodecError::InvalidInteger(text.to_string()),
+                    }
+                })?;
+                Box::new(parsed)
+            }
+            Conversion::Float => {
+                let parsed = f64::from_str(text)
+                    .map_err(|_| CodecError::InvalidFloat(text.to_string()))?;
+                Box::new(parsed)
+            }
+            Conversion::Boolean => Box::new(Self::parse_boolean(text)?),
+            Conversion::Timestamp => {
+                let parsed = DateTime::parse_from_rfc3339(text)
+                    .map_err(|_| CodecError::InvalidTimestamp {
+                        value: text.to_string(),
+                        pattern: "RFC 3339".to_string(),
+                    })?
+                    .with_timezone(&Utc);
+                Box::new(parsed)
+            }
+            Conversion::TimestampFmt(pattern) => {
+                Box::new(Self::parse_timestamp_fmt(text, pattern)?)
+            }
+            Conversion::TimestampTZFmt(pattern) => {
+                Box::new(Self::parse_timestamp_tz_fmt(text, pattern)?)
+            }
+        };
+        Ok(decoded)
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+/// Builds a [`ScalarCodec`] by inspecting a message's resolved schema.
+pub struct ScalarCodecFactory;
+
+impl CodecFactory for ScalarCodecFactory {
+    fn create_codec(&self, message: &Message) -> Result<Box<dyn Codec>> {
+        let conversion = Conversion::from_schema(&message.schema);
+        Ok(Box::new(ScalarCodec::new(conversion)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn codec(conversion: Conversion) -> ScalarCodec {
+        ScalarCodec::new(conversion)
+    }
+
+    #[tokio::test]
+    async fn decode_any_rejects_empty_payload() {
+        let err = codec(Conversion::Integer)
+            .decode_any(&[])
+            .await
+            .unwrap_err();
+        assert!(matches!(err, crate::error::Error::Codec(CodecError::EmptyPayload)));
+    }
+
+    #[tokio::test]
+    async fn decode_any_passes_bytes_through() {
+        let decoded = codec(Conversion::Bytes)
+            .decode_any(b"wirecrab")
+            .await
+            .unwrap();
+        assert_eq!(*decoded.downcast::<Vec<u8>>().unwrap(), b"wirecrab".to_vec());
+    }
+
+    #[tokio::test]
+    async fn decode_any_parses_integer() {
+        let decoded = codec(Conversion::Integer).decode_any(b"-42").await.unwrap();
+        assert_eq!(*decoded.downcast::<i64>().unwrap(), -42);
+    }
+
+    #[tokio::test]
+    async fn decode_any_reports_overflow_separately_from_invalid_integer() {
+        let overflow = codec(Conversion::Integer)
+            .decode_any(b"99999999999999999999")
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            overflow,
+            crate::error::Error::Codec(CodecError::IntegerOutOfRange(_))
+        ));
+
+        let invalid = codec(Conversion::Integer)
+            .decode_any(b"abc")
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            invalid,
+            crate::error::Error::Codec(CodecError::InvalidInteger(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn decode_any_parses_float() {
+        let decoded = codec(Conversion::Float).decode_any(b"3.5").await.unwrap();
+        assert_eq!(*decoded.downcast::<f64>().unwrap(), 3.5);
+    }
+
+    #[tokio::test]
+    async fn decode_any_rejects_invalid_float() {
+        let err = codec(Conversion::Float).decode_any(b"abc").await.unwrap_err();
+        assert!(matches!(
+            err,
+            crate::error::Error::Codec(CodecError::InvalidFloat(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn decode_any_parses_boolean() {
+        let decoded = codec(Conversion::Boolean).decode_any(b"true").await.unwrap();
+        assert!(*decoded.downcast::<bool>().unwrap());
+    }
+
+    #[tokio::test]
+    async fn decode_any_rejects_invalid_boolean() {
+        let err = codec(Conversion::Boolean)
+            .decode_any(b"yes")
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            crate::error::Error::Codec(CodecError::InvalidBoolean(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn decode_any_parses_rfc3339_timestamp() {
+        let decoded = codec(Conversion::Timestamp)
+            .decode_any(b"2024-01-02T03:04:05Z")
+            .await
+            .unwrap();
+        let parsed = decoded.downcast::<DateTime<Utc>>().unwrap();
+        assert_eq!(parsed.to_rfc3339(), "2024-01-02T03:04:05+00:00");
+    }
+
+    #[tokio::test]
+    async fn decode_any_names_the_pattern_on_unparsable_timestamp() {
+        let err = codec(Conversion::Timestamp)
+            .decode_any(b"not a timestamp")
+            .await
+            .unwrap_err();
+        match err {
+            crate::error::Error::Codec(CodecError::InvalidTimestamp { value, pattern }) => {
+                assert_eq!(value, "not a timestamp");
+                assert_eq!(pattern, "RFC 3339");
+            }
+            other => panic!("expected InvalidTimestamp, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn timestamp_fmt_round_trips_through_encode_and_decode() {
+        let pattern = "%Y-%m-%d %H:%M:%S".to_string();
+        let codec = codec(Conversion::TimestampFmt(pattern));
+        let original = Utc.with_ymd_and_hms(2024, 6, 15, 12, 30, 0).unwrap();
+
+        let encoded = codec.encode_any(&original).await.unwrap();
+        let decoded = codec.decode_any(&encoded).await.unwrap();
+        let roundtripped = *decoded.downcast::<DateTime<Utc>>().unwrap();
+
+        assert_eq!(roundtripped.timestamp(), original.timestamp());
+    }
+
+    #[tokio::test]
+    async fn timestamp_tz_fmt_round_trips_through_encode_and_decode() {
+        let pattern = "%Y-%m-%dT%H:%M:%S%z".to_string();
+        let codec = codec(Conversion::TimestampTZFmt(pattern));
+        let original = Utc.with_ymd_and_hms(2024, 6, 15, 12, 30, 0).unwrap();
+
+        let encoded = codec.encode_any(&original).await.unwrap();
+        let decoded = codec.decode_any(&encoded).await.unwrap();
+        let roundtripped = *decoded.downcast::<DateTime<Utc>>().unwrap();
+
+        assert_eq!(roundtripped, original);
+    }
+}
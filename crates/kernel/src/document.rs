@@ -1,10 +1,29 @@
 use crate::utils::structs::*;
+use crate::wire::ChannelConfig;
 
+#[derive(Clone)]
 pub struct Document {
     pub operations: BTreeMap<String, Operation>,
 }
 
-pub struct Operation;
+/// A resolved `operations.<id>` entry: enough to tell a [`crate::wire::Wire`] which channel to
+/// bind, whether to send or receive on it, and with which `ChannelConfig`.
+///
+/// `PartialEq` compares the channel and action *and* the full `ChannelConfig`, so a reload diff
+/// (see [`crate::wire::ChannelConfig`]'s own `PartialEq`) classifies a spec edit that only
+/// changes a channel's SASL mechanism or credentials as a modification, not a no-op.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Operation {
+    pub channel: String,
+    pub action: OperationKind,
+    pub config: ChannelConfig,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OperationKind {
+    Send,
+    Receive,
+}
 
 pub struct Channel;
 
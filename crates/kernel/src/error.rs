@@ -1,7 +1,43 @@
 use crate::utils::structs::*;
 
+pub type Result<T> = core::result::Result<T, Error>;
+
 #[derive(Debug, ThisError)]
 pub enum Error {
     #[error("Wire error: {0}")]
     Wire(#[from] AnyhowError),
+    #[error("Codec error: {0}")]
+    Codec(#[from] CodecError),
+    #[error("Connection error: {0}")]
+    Connection(#[from] ConnectionError),
+}
+
+#[derive(Debug, ThisError)]
+pub enum ConnectionError {
+    #[error("SASL authentication failed: {0}")]
+    SaslAuth(#[from] crate::auth::SaslError),
+}
+
+#[derive(Debug, ThisError)]
+pub enum CodecError {
+    #[error("cannot decode an empty payload")]
+    EmptyPayload,
+    #[error("payload is not valid UTF-8: {0}")]
+    InvalidUtf8(#[from] core::str::Utf8Error),
+    #[error("integer value '{0}' is out of range")]
+    IntegerOutOfRange(String),
+    #[error("invalid integer value '{0}'")]
+    InvalidInteger(String),
+    #[error("invalid float value '{0}'")]
+    InvalidFloat(String),
+    #[error("invalid boolean value '{0}'")]
+    InvalidBoolean(String),
+    #[error("invalid timestamp '{value}', expected pattern '{pattern}'")]
+    InvalidTimestamp { value: String, pattern: String },
+    #[error("decryption failed: authentication tag mismatch")]
+    DecryptionFailed,
+    #[error("missing AEAD header '{0}'")]
+    MissingAeadHeader(&'static str),
+    #[error("unsupported AEAD algorithm '{0}'")]
+    UnsupportedAlgorithm(String),
 }
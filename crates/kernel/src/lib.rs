@@ -1,13 +1,18 @@
 #![forbid(unsafe_code)]
 
 pub mod application;
+pub mod auth;
 pub mod codec;
+pub mod document;
 pub mod endpoint;
 pub mod error;
 pub mod models;
+pub mod prelude;
+mod utils;
 pub mod wire;
 
 pub use application::{Application, ApplicationBuilder, ApplicationRuntime};
+pub use auth::{PlainMechanism, SaslConfig, SaslCredentials, SaslMechanism, ScramSha256Mechanism};
 pub use codec::{Codec, CodecFactory};
 pub use endpoint::{Consumer, Producer, RpcClient, RpcHandler, RpcServer};
 pub use error::{ApplicationError, CodecError, ConnectionError, EndpointError, Error, Result};
@@ -0,0 +1,33 @@
+use crate::utils::structs::*;
+
+pub mod operation;
+
+pub use operation::{Action, Reply};
+
+pub struct Channel;
+
+pub struct Operation;
+
+#[derive(Debug, Default)]
+pub struct OperationBindings;
+
+#[derive(Debug, Clone)]
+pub enum ParameterValue {
+    String(String),
+    Integer(i64),
+}
+
+/// A message as seen by the codec layer: the bits of its resolved schema a
+/// [`crate::codec::CodecFactory`] needs to pick a wire-level conversion. It carries no payload.
+pub struct Message {
+    pub schema: MessageSchema,
+}
+
+/// Scalar portion of a resolved message schema, i.e. `type`/`format` plus any `x-*` extensions,
+/// without pulling in a full JSON Schema representation.
+#[derive(Debug, Clone, Default)]
+pub struct MessageSchema {
+    pub r#type: Option<String>,
+    pub format: Option<String>,
+    pub extensions: BTreeMap<String, String>,
+}
@@ -0,0 +1,3 @@
+pub struct Action;
+
+pub struct Reply;
@@ -0,0 +1,3 @@
+//! Common imports shared across this crate and its downstream consumers (e.g. `wirecrab-spec`).
+
+pub use crate::utils::structs::*;
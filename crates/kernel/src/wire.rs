@@ -8,6 +8,15 @@ pub trait Sender: Lifecycle {
 #[async_trait]
 pub trait Receiver: Lifecycle {
     async fn receive(&mut self) -> Result<WireMessage, AnyhowError>;
+
+    /// Non-blocking receive: returns `Ok(None)` immediately if no message is buffered yet,
+    /// instead of awaiting the next one like `receive`.
+    async fn try_receive(&mut self) -> Result<Option<WireMessage>, AnyhowError>;
+
+    /// Resolves once the underlying transport has a message ready to `receive`/`try_receive`.
+    /// Lets callers `select!` across many `Receiver`s, or build a single-threaded scheduler over
+    /// many of them without dedicating a task per channel.
+    async fn readable(&self) -> Result<(), AnyhowError>;
 }
 
 #[async_trait]
@@ -34,4 +43,9 @@ pub struct WireMessage {
 }
 
 /// Configuration for creating Sender/Receiver endpoints
-pub struct ChannelConfig;
+#[derive(Clone, PartialEq, Eq)]
+pub struct ChannelConfig {
+    /// SASL mechanism and credentials to negotiate during `Lifecycle::start`, if the channel's
+    /// `securitySchemes` requires authentication.
+    pub sasl: Option<crate::auth::SaslConfig>,
+}
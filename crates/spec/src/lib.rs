@@ -7,6 +7,7 @@
 //! It operates on [`kernel::document`] types from wirecrab-kernel crate.
 
 pub mod ast;
+pub mod reload;
 pub mod resolver;
 pub use kernel::document::*;
 use serde_json::Value;
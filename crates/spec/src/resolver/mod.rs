@@ -24,6 +24,8 @@ pub enum RefError {
     Parse(#[from] serde_yaml::Error),
     #[error("HTTP request failed: {0}")]
     Http(String),
+    #[error("circular $ref detected: {}", .0.join(" -> "))]
+    Cycle(Vec<String>),
 }
 
 pub type RefResult = Result<Shared<Value>, RefError>;
@@ -107,6 +109,20 @@ pub struct RefResolver {
 }
 
 impl RefResolver {
+    /// Every `DocLocation` currently cached, i.e. every file/URL this resolver has loaded while
+    /// servicing `resolve`/`resolve_ref` calls so far.
+    pub fn tracked_locations(&self) -> Vec<DocLocation> {
+        self.docs.borrow().keys().cloned().collect()
+    }
+
+    /// Drop the cached document at `location`, along with any subtrees resolved from it, so the
+    /// next `resolve`/`resolve_ref` touching it re-reads and re-parses it from scratch. Other
+    /// locations' caches are left untouched.
+    pub fn invalidate(&self, location: &DocLocation) {
+        self.docs.borrow_mut().remove(location);
+        self.subtrees.borrow_mut().retain(|(loc, _), _| loc != location);
+    }
+
     pub fn resolve(&self, doc_ref: DocumentRef) -> RefResult {
         let cache_key = ((*doc_ref.location).clone(), (*doc_ref.addr).clone());
 
@@ -199,6 +215,125 @@ impl RefResolver {
         }
     }
 
+    /// Like [`RefResolver::resolve`], but also follows every `$ref` encountered inside the
+    /// resolved subtree — including ones pointing at a different file/URL — and splices each in
+    /// place, producing a fully dereferenced subtree.
+    ///
+    /// A `$ref` with no `path#...`/`https://...#...` prefix (i.e. a bare `#/...`) inherits
+    /// `doc_ref`'s `DocLocation`; a relative file ref is resolved against the referring
+    /// document's directory. Re-entering a `(DocLocation, DocAddress)` already on the recursion
+    /// stack is a cycle and returns [`RefError::Cycle`] naming the chain that led back to it.
+    pub fn resolve_deep(&self, doc_ref: DocumentRef) -> Result<Value, RefError> {
+        let mut stack = Vec::new();
+        self.resolve_deep_on_stack(doc_ref, &mut stack)
+    }
+
+    fn resolve_deep_on_stack(
+        &self,
+        doc_ref: DocumentRef,
+        stack: &mut Vec<(DocLocation, DocAddress)>,
+    ) -> Result<Value, RefError> {
+        let key = ((*doc_ref.location).clone(), (*doc_ref.addr).clone());
+
+        if let Some(start) = stack.iter().position(|entry| entry == &key) {
+            let mut chain: Vec<String> = stack[start..]
+                .iter()
+                .map(|(loc, addr)| Self::describe_ref(loc, addr))
+                .collect();
+            chain.push(Self::describe_ref(&key.0, &key.1));
+            return Err(RefError::Cycle(chain));
+        }
+
+        stack.push(key);
+        let value = self.resolve(doc_ref.clone())?;
+        let spliced = self.splice_refs(&value, &doc_ref.location, stack);
+        stack.pop();
+        spliced
+    }
+
+    fn splice_refs(
+        &self,
+        value: &Value,
+        current_location: &DocLocation,
+        stack: &mut Vec<(DocLocation, DocAddress)>,
+    ) -> Result<Value, RefError> {
+        match value {
+            Value::Object(map) => {
+                if let Some(ref_str) = map.get("$ref").and_then(|v| v.as_str()) {
+                    let next_ref = Self::parse_nested_ref(ref_str, current_location)?;
+                    self.resolve_deep_on_stack(next_ref, stack)
+                } else {
+                    let mut new_map = serde_json::Map::new();
+                    for (key, val) in map {
+                        new_map.insert(key.clone(), self.splice_refs(val, current_location, stack)?);
+                    }
+                    Ok(Value::Object(new_map))
+                }
+            }
+            Value::Array(items) => {
+                let mut new_items = Vec::with_capacity(items.len());
+                for item in items {
+                    new_items.push(self.splice_refs(item, current_location, stack)?);
+                }
+                Ok(Value::Array(new_items))
+            }
+            _ => Ok(value.clone()),
+        }
+    }
+
+    /// Parse a `$ref` string encountered while splicing `current_location`'s subtree into a
+    /// `DocumentRef`, switching `DocLocation` when the ref targets another file/URL and resolving
+    /// relative file refs against `current_location`'s directory.
+    fn parse_nested_ref(
+        ref_str: &str,
+        current_location: &DocLocation,
+    ) -> Result<DocumentRef, RefError> {
+        let (loc_part, addr_part) = ref_str
+            .split_once('#')
+            .ok_or_else(|| RefError::Http("Invalid ref format".to_string()))?;
+
+        let location = if loc_part.is_empty() {
+            current_location.clone()
+        } else if let Ok(url) = Url::parse(loc_part) {
+            DocLocation::Url(url)
+        } else {
+            let path = PathBuf::from(loc_part);
+            let resolved_path = if path.is_relative() {
+                match current_location {
+                    DocLocation::File(base) => base
+                        .parent()
+                        .map(|dir| dir.join(&path))
+                        .unwrap_or_else(|| path.clone()),
+                    DocLocation::Url(_) => path.clone(),
+                }
+            } else {
+                path
+            };
+            DocLocation::File(resolved_path)
+        };
+
+        let addr_str = if addr_part.starts_with('#') {
+            addr_part.to_string()
+        } else {
+            format!("#{}", addr_part)
+        };
+        let addr =
+            DocAddress::try_from(addr_str.as_str()).map_err(|e| RefError::Http(e.to_string()))?;
+
+        Ok(DocumentRef {
+            location: Shared::new(location),
+            addr: Shared::new(addr),
+        })
+    }
+
+    fn describe_ref(location: &DocLocation, addr: &DocAddress) -> String {
+        let pointer = addr.iter().collect::<Vec<_>>().join("/");
+        match location {
+            DocLocation::File(path) => format!("{}#/{}", path.display(), pointer),
+            DocLocation::Url(url) => format!("{}#/{}", url, pointer),
+        }
+    }
+
     fn traverse_and_clone(
         &self,
         doc: &Value,
@@ -388,6 +388,65 @@ a/b:
     assert_eq!(doc_value, &Value::String("object".to_string()));
 }
 
+#[test]
+fn refresolver_invalidate_evicts_doc_and_subtree_cache() {
+    let yaml = r#"
+components:
+  schemas:
+    User:
+      type: object
+"#;
+    let file = create_test_yaml(yaml);
+    let location = DocLocation::File(file.path().to_path_buf());
+
+    let resolver = RefResolver::default();
+    let doc_ref = DocumentRef {
+        location: Shared::new(location.clone()),
+        addr: Shared::new(DocAddress::try_from("#/components/schemas/User").unwrap()),
+    };
+    resolver.resolve(doc_ref.clone()).unwrap();
+
+    assert!(resolver.tracked_locations().contains(&location));
+
+    resolver.invalidate(&location);
+
+    assert!(!resolver.tracked_locations().contains(&location));
+    assert!(resolver.subtrees.borrow().is_empty());
+
+    // Re-resolving after invalidation re-reads the file rather than erroring.
+    assert!(resolver.resolve(doc_ref).is_ok());
+}
+
+#[test]
+fn refresolver_invalidate_leaves_other_locations_untouched() {
+    let yaml_a = "components:\n  schemas:\n    User:\n      type: object\n";
+    let yaml_b = "components:\n  schemas:\n    Admin:\n      type: object\n";
+    let file_a = create_test_yaml(yaml_a);
+    let file_b = create_test_yaml(yaml_b);
+    let location_a = DocLocation::File(file_a.path().to_path_buf());
+    let location_b = DocLocation::File(file_b.path().to_path_buf());
+
+    let resolver = RefResolver::default();
+    resolver
+        .resolve(DocumentRef {
+            location: Shared::new(location_a.clone()),
+            addr: Shared::new(DocAddress::try_from("#/components/schemas/User").unwrap()),
+        })
+        .unwrap();
+    resolver
+        .resolve(DocumentRef {
+            location: Shared::new(location_b.clone()),
+            addr: Shared::new(DocAddress::try_from("#/components/schemas/Admin").unwrap()),
+        })
+        .unwrap();
+
+    resolver.invalidate(&location_a);
+
+    let tracked = resolver.tracked_locations();
+    assert!(!tracked.contains(&location_a));
+    assert!(tracked.contains(&location_b));
+}
+
 #[test]
 fn refresolver_with_tilde_escaped_in_path() {
     let yaml = r#"
@@ -412,3 +471,82 @@ a~b:
     let doc_value = doc.as_ref();
     assert_eq!(doc_value, &Value::String("object".to_string()));
 }
+
+#[test]
+fn resolve_deep_splices_local_ref() {
+    let yaml = r#"
+components:
+  schemas:
+    User:
+      name:
+        $ref: '#/components/schemas/Name'
+    Name:
+      type: string
+"#;
+    let file = create_test_yaml(yaml);
+
+    let resolver = RefResolver::default();
+    let doc_ref = DocumentRef {
+        location: Shared::new(DocLocation::File(file.path().to_path_buf())),
+        addr: Shared::new(DocAddress::try_from("#/components/schemas/User").unwrap()),
+    };
+
+    let resolved = resolver.resolve_deep(doc_ref).unwrap();
+    assert_eq!(
+        resolved.get("name").and_then(|name| name.get("type")),
+        Some(&Value::String("string".to_string()))
+    );
+}
+
+#[test]
+fn resolve_deep_follows_ref_into_another_file() {
+    let dir = tempfile::tempdir().unwrap();
+
+    let shared_path = dir.path().join("shared.yaml");
+    fs::write(
+        &shared_path,
+        "components:\n  schemas:\n    Name:\n      type: string\n",
+    )
+    .unwrap();
+
+    let root_path = dir.path().join("root.yaml");
+    fs::write(
+        &root_path,
+        "components:\n  schemas:\n    User:\n      name:\n        $ref: shared.yaml#/components/schemas/Name\n",
+    )
+    .unwrap();
+
+    let resolver = RefResolver::default();
+    let doc_ref = DocumentRef {
+        location: Shared::new(DocLocation::File(root_path)),
+        addr: Shared::new(DocAddress::try_from("#/components/schemas/User").unwrap()),
+    };
+
+    let resolved = resolver.resolve_deep(doc_ref).unwrap();
+    assert_eq!(
+        resolved.get("name").and_then(|name| name.get("type")),
+        Some(&Value::String("string".to_string()))
+    );
+}
+
+#[test]
+fn resolve_deep_detects_cycle() {
+    let yaml = r#"
+components:
+  schemas:
+    A:
+      $ref: '#/components/schemas/B'
+    B:
+      $ref: '#/components/schemas/A'
+"#;
+    let file = create_test_yaml(yaml);
+
+    let resolver = RefResolver::default();
+    let doc_ref = DocumentRef {
+        location: Shared::new(DocLocation::File(file.path().to_path_buf())),
+        addr: Shared::new(DocAddress::try_from("#/components/schemas/A").unwrap()),
+    };
+
+    let result = resolver.resolve_deep(doc_ref);
+    assert!(matches!(result, Err(RefError::Cycle(_))));
+}